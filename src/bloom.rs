@@ -0,0 +1,79 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::BitOr;
+
+/// Fixed-width bloom filter, generic over its byte width `N`.
+///
+/// Defaults to 256 bytes (2048 bits) to stay compatible with Ethereum-style
+/// log blooms, but callers indexing non-Ethereum data can pick a smaller or
+/// larger width.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bloom<const N: usize = 256>([u8; N]);
+
+impl<const N: usize> Default for Bloom<N> {
+	fn default() -> Self {
+		Bloom([0u8; N])
+	}
+}
+
+impl<const N: usize> From<[u8; N]> for Bloom<N> {
+	fn from(bytes: [u8; N]) -> Self {
+		Bloom(bytes)
+	}
+}
+
+/// Returned by `TryFrom<&[u8]>` when a slice's length doesn't match the bloom's width.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LengthMismatch {
+	/// Width the bloom expects.
+	pub expected: usize,
+	/// Width of the slice that was given.
+	pub got: usize,
+}
+
+impl fmt::Display for LengthMismatch {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "expected a {} byte slice, got {}", self.expected, self.got)
+	}
+}
+
+impl<'a, const N: usize> TryFrom<&'a [u8]> for Bloom<N> {
+	type Error = LengthMismatch;
+
+	fn try_from(slice: &'a [u8]) -> Result<Self, Self::Error> {
+		if slice.len() != N {
+			return Err(LengthMismatch { expected: N, got: slice.len() });
+		}
+
+		let mut bytes = [0u8; N];
+		bytes.copy_from_slice(slice);
+		Ok(Bloom(bytes))
+	}
+}
+
+impl<const N: usize> Bloom<N> {
+	/// Returns true if this bloom contains given bloom.
+	pub fn contains(&self, other: &Bloom<N>) -> bool {
+		self.0.iter().zip(other.0.iter()).all(|(a, b)| a & b == *b)
+	}
+}
+
+impl<'a, const N: usize> BitOr<&'a Bloom<N>> for &'a Bloom<N> {
+	type Output = Bloom<N>;
+
+	fn bitor(self, rhs: &'a Bloom<N>) -> Bloom<N> {
+		let mut result = [0u8; N];
+		for (r, (a, b)) in result.iter_mut().zip(self.0.iter().zip(rhs.0.iter())) {
+			*r = a | b;
+		}
+		Bloom(result)
+	}
+}
+
+impl<const N: usize> BitOr<Bloom<N>> for Bloom<N> {
+	type Output = Bloom<N>;
+
+	fn bitor(self, rhs: Bloom<N>) -> Bloom<N> {
+		&self | &rhs
+	}
+}
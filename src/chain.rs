@@ -1,38 +1,53 @@
 use std::collections::{HashMap, HashSet};
-use std::ops::Range;
+use std::ops::{Range, RangeInclusive};
 use number::Number;
 use position::{Position, Manager as PositionManager};
 use bloom::Bloom;
 use filter::Filter;
-use config::Config;
+use config::{Config, Error as ConfigError};
 use database::BloomDatabase;
 
 /// Prepares all bloom database operations.
-pub struct BloomChain<'a> {
+///
+/// Generic over the width `N` of the blooms it indexes, so the chain, its
+/// backing `BloomDatabase`, and its `Filter` all agree on the same bloom type.
+pub struct BloomChain<'a, const N: usize = 256> {
 	positioner: PositionManager,
-	db: &'a BloomDatabase,
+	db: &'a dyn BloomDatabase<N>,
 }
 
-impl<'a> BloomChain<'a> {
-	/// Creates new bloom chain.
-	pub fn new(config: Config, db: &'a BloomDatabase) -> Self {
+impl<'a, const N: usize> BloomChain<'a, N> {
+	/// Creates new bloom chain, validating `config` up front.
+	///
+	/// Returns an error instead of panicking mid-query on a misconfigured
+	/// `levels` or `elements_per_index`.
+	pub fn new(config: Config, db: &'a dyn BloomDatabase<N>) -> Result<Self, ConfigError> {
+		config.validate()?;
+
 		let positioner = PositionManager::new(config.elements_per_index, config.levels);
 
-		BloomChain {
+		Ok(BloomChain {
 			positioner: positioner,
 			db: db,
-		}
+		})
 	}
 
 	/// Internal function which does bloom search recursively.
-	fn blocks(&self, range: &Range<Number>, bloom: &Bloom, level: usize, offset: usize) -> Option<Vec<usize>> {
+	fn blocks(&self, range: &Range<Number>, bloom: &Bloom<N>, level: usize, offset: usize) -> Option<Vec<usize>> {
 		let index = self.positioner.position(offset, level);
 
 		match self.db.bloom_at(&index) {
 			None => return None,
 			Some(level_bloom) => match level {
 				// if we are on the lowest level
-				0 if level_bloom.contains(bloom) => return Some(vec![offset]),
+				0 if level_bloom.contains(bloom) => {
+					// exclude matches that fell out of the [start, end) range
+					return if offset >= range.start && offset < range.end {
+						Some(vec![offset])
+					} else {
+						None
+					};
+				},
 				// return None if current level doesnt contain given bloom
 				_ if !level_bloom.contains(bloom) => return None,
 				// continue processing && go down
@@ -42,7 +57,8 @@ impl<'a> BloomChain<'a> {
 
 		let level_size = self.positioner.level_size(level - 1);
 		let from_position = self.positioner.position(range.start, level - 1);
-		let to_position = self.positioner.position(range.end, level - 1);
+		// range.end is exclusive, so the last included block lives at range.end - 1
+		let to_position = self.positioner.position(range.end - 1, level - 1);
 		let res: Vec<usize> = self.positioner.lower_level_positions(&index).into_iter()
 			// chose only blooms in range
 			.filter(|li| li.index >= from_position.index && li.index <= to_position.index)
@@ -58,8 +74,8 @@ impl<'a> BloomChain<'a> {
 	}
 
 	/// Inserts the bloom at all filter levels.
-	pub fn insert(&self, number: Number, bloom: Bloom) -> HashMap<Position, Bloom> {
-		let mut result: HashMap<Position, Bloom> = HashMap::new();
+	pub fn insert(&self, number: Number, bloom: Bloom<N>) -> HashMap<Position, Bloom<N>> {
+		let mut result: HashMap<Position, Bloom<N>> = HashMap::new();
 
 		for level in 0..self.positioner.levels() {
 			let position = self.positioner.position(number, level);
@@ -74,11 +90,54 @@ impl<'a> BloomChain<'a> {
 		result
 	}
 
+	/// Inserts a contiguous run of blooms starting from `start` in one pass.
+	///
+	/// This has replace, not OR, semantics at level 0: unlike `insert`, which
+	/// ORs the new bloom into whatever is already at a position, this
+	/// overwrites level 0 for the given range outright. Every higher level
+	/// index touched by the range is then rebuilt from its (possibly
+	/// overwritten) children, recomputing each index only once rather than
+	/// once per block that falls under it.
+	pub fn insert_blooms(&self, start: Number, blooms: Vec<Bloom<N>>) -> HashMap<Position, Bloom<N>> {
+		let mut result: HashMap<Position, Bloom<N>> = HashMap::new();
+
+		// insert all new blooms at level 0
+		for (i, bloom) in blooms.iter().enumerate() {
+			result.insert(self.positioner.position(start + i, 0), bloom.clone());
+		}
+
+		for level in 1..self.positioner.levels() {
+			// every index at this level touched by the inserted range
+			let affected_indices = (0..blooms.len())
+				.map(|i| self.positioner.position(start + i, level))
+				.collect::<HashSet<Position>>();
+
+			for index in affected_indices {
+				let new_bloom = {
+					// use new blooms before db blooms where necessary
+					let bloom_at = |index| result.get(&index).cloned().or_else(|| self.db.bloom_at(&index));
+
+					self.positioner.lower_level_positions(&index)
+						.into_iter()
+						// get blooms
+						// filter existing ones
+						.filter_map(bloom_at)
+						// BitOr all of them
+						.fold(Bloom::default(), |acc, bloom| acc | bloom)
+				};
+
+				result.insert(index, new_bloom);
+			}
+		}
+
+		result
+	}
+
 	/// Resets data in range.
 	/// Inserts new data.
 	/// Inserted data may exceed reseted range.
-	pub fn replace(&self, range: &Range<Number>, blooms: Vec<Bloom>) -> HashMap<Position, Bloom> {
-		let mut result: HashMap<Position, Bloom> = HashMap::new();
+	pub fn replace(&self, range: &Range<Number>, blooms: Vec<Bloom<N>>) -> HashMap<Position, Bloom<N>> {
+		let mut result: HashMap<Position, Bloom<N>> = HashMap::new();
 
 		// insert all new blooms at level 0
 		for (i, bloom) in blooms.iter().enumerate() {
@@ -86,7 +145,7 @@ impl<'a> BloomChain<'a> {
 		}
 
 		// reset the rest of blooms
-		for reset_number in range.start + blooms.len()..(range.end + 1) {
+		for reset_number in range.start + blooms.len()..range.end {
 			result.insert(self.positioner.position(reset_number, 0), Bloom::default());
 		}
 
@@ -114,14 +173,18 @@ impl<'a> BloomChain<'a> {
 		result
 	}
 
-	/// Returns all numbers with given bloom.
-	pub fn with_bloom(&self, range: &Range<Number>, bloom: &Bloom) -> Vec<Number> {
+	/// Returns all numbers with given bloom in the half-open range `[range.start, range.end)`.
+	pub fn with_bloom(&self, range: &Range<Number>, bloom: &Bloom<N>) -> Vec<Number> {
 		let mut result = vec![];
+		if range.start >= range.end {
+			return result;
+		}
+
 		// lets start from highest level
 		let max_level = self.positioner.max_level();
 		let level_size = self.positioner.level_size(max_level);
 		let from_position = self.positioner.position(range.start, max_level);
-		let to_position = self.positioner.position(range.end, max_level);
+		let to_position = self.positioner.position(range.end - 1, max_level);
 
 		for index in from_position.index..to_position.index + 1 {
 			// offset will be used to calculate where we are right now
@@ -136,8 +199,14 @@ impl<'a> BloomChain<'a> {
 		result
 	}
 
+	/// Like `with_bloom`, but treats `range` as closed (`[start, end]`) rather than half-open.
+	pub fn with_bloom_closed(&self, range: &RangeInclusive<Number>, bloom: &Bloom<N>) -> Vec<Number> {
+		self.with_bloom(&(*range.start()..range.end() + 1), bloom)
+	}
+
 	/// Filter the chain returing all numbers matching the filter.
-	pub fn filter(&self, filter: &Filter) -> Vec<Number> {
+	/// Honors `filter.range()`'s half-open `[from_block, to_block)` contract.
+	pub fn filter(&self, filter: &Filter<N>) -> Vec<Number> {
 		let range = filter.range();
 		let mut blocks = filter.bloom_possibilities()
 			.into_iter()
@@ -149,4 +218,39 @@ impl<'a> BloomChain<'a> {
 		blocks.sort();
 		blocks
 	}
+
+	/// Like `filter`, but returns a lazy iterator instead of collecting every
+	/// match up front.
+	///
+	/// Walks the top level index by index, descending into each one only as
+	/// the iterator is driven, and deduplicates matches across the filter's
+	/// `bloom_possibilities()` on the fly. Lets callers `take(n)` or
+	/// short-circuit without paying for the whole result set.
+	pub fn filter_iter<'b>(&'b self, filter: &'b Filter<N>) -> impl Iterator<Item = Number> + 'b {
+		let range = filter.range();
+		let blooms = filter.bloom_possibilities();
+		let max_level = self.positioner.max_level();
+		let level_size = self.positioner.level_size(max_level);
+
+		let indices: Range<usize> = if range.start >= range.end {
+			0..0
+		} else {
+			let from_index = self.positioner.position(range.start, max_level).index;
+			let to_index = self.positioner.position(range.end - 1, max_level).index;
+			from_index..to_index + 1
+		};
+
+		let mut seen = HashSet::new();
+
+		indices
+			.flat_map(move |index| {
+				let offset = level_size * index;
+				blooms.iter()
+					.filter_map(|bloom| self.blocks(&range, bloom, max_level, offset))
+					.flatten()
+					.collect::<Vec<Number>>()
+			})
+			// deduplicate across bloom_possibilities as matches are found
+			.filter(move |number| seen.insert(*number))
+	}
 }
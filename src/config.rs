@@ -0,0 +1,43 @@
+use std::fmt;
+
+/// Bloom chain configuration.
+#[derive(Debug, Clone)]
+pub struct Config {
+	/// Number of elements indexed by a single position at the level below.
+	pub elements_per_index: usize,
+	/// Number of levels in the chain.
+	pub levels: usize,
+}
+
+/// Describes what is wrong with a `Config`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+	/// `levels` must be at least 1.
+	ZeroLevels,
+	/// `elements_per_index` must be at least 1, or computing a position would divide by zero.
+	ZeroElementsPerIndex,
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			Error::ZeroLevels => write!(f, "config.levels must be greater than zero"),
+			Error::ZeroElementsPerIndex => write!(f, "config.elements_per_index must be greater than zero"),
+		}
+	}
+}
+
+impl Config {
+	/// Checks that this configuration can be used to build a `BloomChain` without panicking.
+	pub fn validate(&self) -> Result<(), Error> {
+		if self.levels == 0 {
+			return Err(Error::ZeroLevels);
+		}
+
+		if self.elements_per_index == 0 {
+			return Err(Error::ZeroElementsPerIndex);
+		}
+
+		Ok(())
+	}
+}
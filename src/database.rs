@@ -0,0 +1,37 @@
+use std::collections::HashMap;
+use position::Position;
+use bloom::Bloom;
+
+/// Readonly bloom database, generic over the width `N` of the blooms it stores.
+pub trait BloomDatabase<const N: usize = 256> {
+	/// Returns the bloom at given position, or `None` if it was never set.
+	fn bloom_at(&self, position: &Position) -> Option<Bloom<N>>;
+}
+
+/// In-memory bloom database backed by a `HashMap`.
+///
+/// Useful for tests and for feeding the results of `BloomChain::insert` /
+/// `replace` / `insert_blooms` straight back into a readable store.
+#[derive(Debug, Default)]
+pub struct MemoryDatabase<const N: usize = 256> {
+	map: HashMap<Position, Bloom<N>>,
+}
+
+impl<const N: usize> MemoryDatabase<N> {
+	/// Creates new, empty database.
+	pub fn new() -> Self {
+		MemoryDatabase::default()
+	}
+
+	/// Inserts given blooms into the database, overwriting any blooms already
+	/// present at the same positions.
+	pub fn insert_blooms(&mut self, blooms: HashMap<Position, Bloom<N>>) {
+		self.map.extend(blooms);
+	}
+}
+
+impl<const N: usize> BloomDatabase<N> for MemoryDatabase<N> {
+	fn bloom_at(&self, position: &Position) -> Option<Bloom<N>> {
+		self.map.get(position).cloned()
+	}
+}
@@ -0,0 +1,25 @@
+use std::ops::Range;
+use number::Number;
+use bloom::Bloom;
+
+/// Describes a bloom search query, generic over the width `N` of the blooms it matches.
+pub struct Filter<const N: usize = 256> {
+	/// First block number to search from (inclusive).
+	pub from_block: Number,
+	/// Last block number to search to (exclusive).
+	pub to_block: Number,
+	/// Blooms that a matching block must contain at least one of.
+	pub blooms: Vec<Bloom<N>>,
+}
+
+impl<const N: usize> Filter<N> {
+	/// Returns the range of blocks this filter searches.
+	pub fn range(&self) -> Range<Number> {
+		self.from_block..self.to_block
+	}
+
+	/// Returns all the possible blooms that satisfy this filter.
+	pub fn bloom_possibilities(&self) -> Vec<Bloom<N>> {
+		self.blooms.clone()
+	}
+}
@@ -0,0 +1,15 @@
+mod bloom;
+mod chain;
+mod config;
+mod database;
+mod filter;
+mod number;
+mod position;
+
+pub use bloom::{Bloom, LengthMismatch};
+pub use chain::BloomChain;
+pub use config::{Config, Error as ConfigError};
+pub use database::{BloomDatabase, MemoryDatabase};
+pub use filter::Filter;
+pub use number::Number;
+pub use position::Position;
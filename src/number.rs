@@ -0,0 +1,2 @@
+/// A block number.
+pub type Number = usize;
@@ -0,0 +1,70 @@
+/// Specifies position of the bloom in the database.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Position {
+	/// Level of the position.
+	pub level: usize,
+	/// Index of the position at the given level.
+	pub index: usize,
+}
+
+/// Computes bloom positions for a chain with given parameters.
+///
+/// The size of every level is precomputed once; positions themselves are
+/// computed on each call and not cached.
+///
+/// Assumes `levels > 0` and `elements_per_index > 0`; callers are expected to
+/// validate a `Config` before building a `Manager` from it.
+pub struct Manager {
+	elements_per_index: usize,
+	levels: usize,
+	// level_sizes[level] == elements_per_index.pow(level), precomputed once
+	// instead of on every `level_size` call.
+	level_sizes: Vec<usize>,
+}
+
+impl Manager {
+	/// Creates new positions manager.
+	pub fn new(elements_per_index: usize, levels: usize) -> Self {
+		let level_sizes = (0..levels)
+			.map(|level| elements_per_index.pow(level as u32))
+			.collect();
+
+		Manager {
+			elements_per_index,
+			levels,
+			level_sizes,
+		}
+	}
+
+	/// Returns number of levels.
+	pub fn levels(&self) -> usize {
+		self.levels
+	}
+
+	/// Returns the highest level.
+	pub fn max_level(&self) -> usize {
+		self.levels - 1
+	}
+
+	/// Returns number of elements a single index on given level covers.
+	pub fn level_size(&self, level: usize) -> usize {
+		self.level_sizes[level]
+	}
+
+	/// Returns position for the given offset on given level.
+	pub fn position(&self, offset: usize, level: usize) -> Position {
+		Position {
+			level,
+			index: offset / self.level_size(level),
+		}
+	}
+
+	/// Returns all of the lower level positions that compose given position.
+	pub fn lower_level_positions(&self, position: &Position) -> Vec<Position> {
+		let lower_level = position.level - 1;
+		let offset = position.index * self.elements_per_index;
+		(0..self.elements_per_index)
+			.map(|i| Position { level: lower_level, index: offset + i })
+			.collect()
+	}
+}
@@ -0,0 +1,101 @@
+extern crate bloomchain;
+extern crate rustc_serialize;
+
+mod util;
+
+use std::convert::TryFrom;
+use bloomchain::{Bloom, BloomChain, Config, ConfigError, Filter, LengthMismatch, MemoryDatabase};
+use util::from_hex::FromHex;
+
+/// A bloom that matches (and is contained by) itself and any other full bloom.
+fn full_bloom() -> Bloom {
+	Bloom::try_from(&[0xffu8; 256][..]).unwrap()
+}
+
+#[test]
+fn from_hex_matches_full_bloom() {
+	let hex: String = ::std::iter::repeat('f').take(512).collect();
+	assert_eq!(Bloom::from_hex(&hex), full_bloom());
+}
+
+/// Builds a database with `count` consecutive full blooms starting at block 0,
+/// using `insert_blooms` the same way a syncing client would.
+fn seeded_chain(elements_per_index: usize, levels: usize, count: usize) -> (MemoryDatabase, Config) {
+	let config = Config { elements_per_index: elements_per_index, levels: levels };
+	let mut db = MemoryDatabase::new();
+
+	{
+		let chain = BloomChain::new(config.clone(), &db).unwrap();
+		let blooms = (0..count).map(|_| full_bloom()).collect();
+		let result = chain.insert_blooms(0, blooms);
+		db.insert_blooms(result);
+	}
+
+	(db, config)
+}
+
+#[test]
+fn with_bloom_excludes_the_end_block() {
+	let (db, config) = seeded_chain(4, 2, 5);
+	let chain = BloomChain::new(config, &db).unwrap();
+
+	let mut result = chain.with_bloom(&(0..4), &full_bloom());
+	result.sort();
+	assert_eq!(result, vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn with_bloom_closed_includes_the_end_block() {
+	let (db, config) = seeded_chain(4, 2, 5);
+	let chain = BloomChain::new(config, &db).unwrap();
+
+	let mut result = chain.with_bloom_closed(&(0..=4), &full_bloom());
+	result.sort();
+	assert_eq!(result, vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn with_bloom_on_an_empty_range_returns_nothing() {
+	let (db, config) = seeded_chain(4, 2, 5);
+	let chain = BloomChain::new(config, &db).unwrap();
+
+	assert_eq!(chain.with_bloom(&(5..5), &full_bloom()), Vec::<usize>::new());
+}
+
+#[test]
+fn filter_iter_dedups_across_bloom_possibilities_and_supports_take() {
+	let (db, config) = seeded_chain(4, 2, 5);
+	let chain = BloomChain::new(config, &db).unwrap();
+
+	let filter = Filter {
+		from_block: 0,
+		to_block: 4,
+		blooms: vec![full_bloom(), full_bloom()],
+	};
+
+	let all: Vec<_> = chain.filter_iter(&filter).collect();
+	assert_eq!(all, vec![0, 1, 2, 3]);
+
+	let first_two: Vec<_> = chain.filter_iter(&filter).take(2).collect();
+	assert_eq!(first_two, vec![0, 1]);
+}
+
+#[test]
+fn new_rejects_zero_levels() {
+	let config = Config { elements_per_index: 16, levels: 0 };
+	let db: MemoryDatabase = MemoryDatabase::new();
+	assert_eq!(BloomChain::new(config, &db).err(), Some(ConfigError::ZeroLevels));
+}
+
+#[test]
+fn new_rejects_zero_elements_per_index() {
+	let config = Config { elements_per_index: 0, levels: 2 };
+	let db: MemoryDatabase = MemoryDatabase::new();
+	assert_eq!(BloomChain::new(config, &db).err(), Some(ConfigError::ZeroElementsPerIndex));
+}
+
+#[test]
+fn bloom_try_from_reports_the_actual_and_expected_length() {
+	let err = Bloom::<256>::try_from(&[0u8; 128][..]).unwrap_err();
+	assert_eq!(err, LengthMismatch { expected: 256, got: 128 });
+}
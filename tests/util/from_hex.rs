@@ -1,4 +1,4 @@
-use std::ptr;
+use std::convert::TryFrom;
 use rustc_serialize::hex::FromHex as RustcFromHex;
 use bloomchain::Bloom;
 
@@ -8,12 +8,7 @@ pub trait FromHex {
 
 impl FromHex for Bloom {
 	fn from_hex(s: &str) -> Self {
-		let mut res = [0u8; 256];
 		let v = s.from_hex().unwrap();
-		assert_eq!(res.len(), v.len());
-		unsafe {
-			ptr::copy(v.as_ptr(), res.as_mut_ptr(), res.len());
-		}
-		From::from(res)
+		Bloom::try_from(v.as_slice()).unwrap()
 	}
 }